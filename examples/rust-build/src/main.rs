@@ -1,57 +1,499 @@
 use std::env;
 use std::fs::File;
 use std::io::Read;
-use serde_json::Value;
-use log::{info, error};
+use std::path::Path;
+use std::time::Instant;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use log::{debug, info, error};
 
-fn main() {
-    // Initialize logging
-    env_logger::init();
+/// The tool's resolved configuration, deserialized from the merged,
+/// schema-validated `Value`. `parameter_2` is mandatory: a missing value
+/// surfaces as a deserialization error naming the field.
+#[derive(Deserialize)]
+struct Config {
+    #[serde(default = "default_parameter_1")]
+    parameter_1: String,
+    parameter_2: Value,
+}
+
+fn default_parameter_1() -> String {
+    "default_value".to_string()
+}
 
-    // Get the configuration file path from the command-line arguments
+fn main() {
     let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        error!("Usage: {} <config.json>", args[0]);
-        std::process::exit(1);
+
+    // Initialize logging before parsing the rest of the arguments, so that
+    // errors raised during parsing itself (missing flag values, unexpected
+    // positional args) are actually printed. --debug is pre-scanned here to
+    // decide the default level; the full parse below still consumes it.
+    let debug_mode = args.iter().any(|arg| arg == "--debug");
+    let mut logger = env_logger::Builder::from_default_env();
+    if debug_mode {
+        logger.filter_level(log::LevelFilter::Debug);
     }
-    let config_file = &args[1];
+    logger.init();
+
+    // Get the positional config argument and optional flags from the command-line arguments
+    let mut config_arg: Option<String> = None;
+    let mut format_override: Option<String> = None;
+    let mut schema_override: Option<String> = None;
+
+    let mut cli_overrides: Vec<String> = Vec::new();
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--debug" => {
+                // already accounted for by the pre-scan above; consume the
+                // flag here so it isn't rejected as an unexpected argument
+            }
+            "--format" => {
+                i += 1;
+                match args.get(i) {
+                    Some(value) => format_override = Some(value.clone()),
+                    None => {
+                        error!("Missing value for --format");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--schema" => {
+                i += 1;
+                match args.get(i) {
+                    Some(value) => schema_override = Some(value.clone()),
+                    None => {
+                        error!("Missing value for --schema");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            "--set" => {
+                i += 1;
+                match args.get(i) {
+                    Some(value) => cli_overrides.push(value.clone()),
+                    None => {
+                        error!("Missing value for --set");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            other => {
+                if config_arg.is_some() {
+                    error!("Unexpected argument: {}", other);
+                    std::process::exit(1);
+                }
+                config_arg = Some(other.to_string());
+            }
+        }
+        i += 1;
+    }
+
+    let config_arg = match config_arg {
+        Some(arg) => arg,
+        None => {
+            error!(
+                "Usage: {} <config.json | {{\"json\":\"object\"}} | key=value,...> [--format <json|yaml|toml|ini>] [--schema <file>] [--set key=value]... [--debug]",
+                args[0]
+            );
+            std::process::exit(1);
+        }
+    };
 
-    // Read and parse the configuration JSON file
-    let config = match load_config(config_file) {
+    // Read and parse the configuration, whether it's a file path, inline JSON,
+    // or comma-separated key=value pairs
+    let stage_start = Instant::now();
+    let config = match parse_config_arg(&config_arg, format_override.as_deref()) {
         Ok(config) => config,
         Err(e) => {
             error!("Failed to load configuration: {}", e);
             std::process::exit(1);
         }
     };
+    log_stage_time("parsed config", stage_start);
 
-    // Assign a long-lived default value
-    let default_parameter_1 = Value::String("default_value".to_string());
-    let parameter_1 = config.get("parameter_1").unwrap_or(&default_parameter_1);
-
-    let parameter_2 = config.get("parameter_2");
+    // Layer overrides on top of the file config: defaults < file < env < CLI
+    let stage_start = Instant::now();
+    let env_overrides = env_overrides();
+    let cli_overrides = match parse_cli_overrides(&cli_overrides) {
+        Ok(overrides) => overrides,
+        Err(e) => {
+            error!("Invalid --set value: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let resolved = merge(defaults(), config);
+    let resolved = merge(resolved, env_overrides);
+    let resolved = merge(resolved, cli_overrides);
 
-    // Check mandatory parameter
-    if parameter_2.is_none() {
-        error!("Error: 'parameter_2' is mandatory but is not provided.");
+    // Validate against the config schema (a --schema file, the config's own
+    // $schema reference, or the built-in default) instead of hand-checking
+    // individual fields
+    let schema = match resolve_schema(schema_override.as_deref(), &resolved) {
+        Ok(schema) => schema,
+        Err(e) => {
+            error!("Failed to load schema: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if let Err(violations) = validate_config(&resolved, &schema) {
+        for violation in &violations {
+            error!("Config validation error at {}: {}", violation.path, violation.message);
+        }
         std::process::exit(1);
     }
 
+    // Deserialize into the strongly-typed config now that it's passed schema
+    // validation, rather than poking at the `Value` with `get`/`unwrap_or`
+    let config: Config = match serde_json::from_value(resolved) {
+        Ok(config) => config,
+        Err(e) => {
+            error!("Failed to load configuration: {}", e);
+            std::process::exit(1);
+        }
+    };
+    log_stage_time("resolved parameters", stage_start);
+
     // Log extracted parameters
-    info!("Parameter 1: {}", parameter_1);
-    info!("Parameter 2: {}", parameter_2.unwrap());
+    info!("Parameter 1: {}", config.parameter_1);
+    info!("Parameter 2: {}", config.parameter_2);
 
     // Perform actions based on the parameters
+    let stage_start = Instant::now();
     info!("Performing actions based on the configuration...");
     // Add logic for processing parameters
+    log_stage_time("performed actions", stage_start);
 
     info!("Configuration evaluation completed successfully.");
 }
 
-fn load_config(config_file: &str) -> Result<Value, Box<dyn std::error::Error>> {
+/// Logs how long `stage` took at debug level, e.g. "parsed config in 1.2ms".
+/// A no-op unless `--debug` (or `RUST_LOG=debug`) is in effect.
+fn log_stage_time(stage: &str, start: Instant) {
+    debug!("{} in {:.1}ms", stage, start.elapsed().as_secs_f64() * 1000.0);
+}
+
+/// A single schema violation, reported with the JSON pointer path of the
+/// offending value so every problem can be surfaced at once.
+struct Violation {
+    path: String,
+    message: String,
+}
+
+/// The schema shipped with the tool: `parameter_2` is mandatory, `parameter_1`
+/// is an optional string. Used whenever no `--schema` file or config
+/// `$schema` reference is given.
+fn default_schema() -> Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "parameter_1": { "type": "string" },
+            "parameter_2": {}
+        },
+        "required": ["parameter_2"]
+    })
+}
+
+/// Resolves which schema to validate against: an explicit `--schema` file
+/// wins, then the config's own `$schema` reference, then the built-in
+/// default.
+fn resolve_schema(
+    schema_override: Option<&str>,
+    config: &Value,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    if let Some(path) = schema_override {
+        return load_schema_file(path);
+    }
+    if let Some(Value::String(path)) = config.get("$schema") {
+        return load_schema_file(path);
+    }
+    Ok(default_schema())
+}
+
+fn load_schema_file(path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut file = File::open(path)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Validates `config` against `schema`, collecting every violation (rather
+/// than stopping at the first) so the caller can report them all at once.
+fn validate_config(config: &Value, schema: &Value) -> Result<(), Vec<Violation>> {
+    let compiled = jsonschema::JSONSchema::compile(schema)
+        .map_err(|e| vec![Violation { path: "$schema".to_string(), message: e.to_string() }])?;
+
+    if let Err(errors) = compiled.validate(config) {
+        let violations = errors
+            .map(|e| Violation {
+                path: e.instance_path.to_string(),
+                message: e.to_string(),
+            })
+            .collect();
+        return Err(violations);
+    }
+    Ok(())
+}
+
+/// Built-in defaults, lowest priority in the override chain.
+fn defaults() -> Value {
+    Value::Object(Map::from_iter([(
+        "parameter_1".to_string(),
+        Value::String("default_value".to_string()),
+    )]))
+}
+
+/// Known config keys and the environment variable that overrides each one.
+/// `DAT_` abbreviates "data-analysis-tools".
+const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("parameter_1", "DAT_PARAMETER_1"),
+    ("parameter_2", "DAT_PARAMETER_2"),
+];
+
+/// Collects whichever of the known config keys have a matching env var set.
+fn env_overrides() -> Value {
+    let mut overrides = Map::new();
+    for (key, var) in ENV_OVERRIDES {
+        if let Ok(value) = env::var(var) {
+            overrides.insert(key.to_string(), Value::String(value));
+        }
+    }
+    Value::Object(overrides)
+}
+
+/// Parses `--set key=value` flags into an overlay object.
+fn parse_cli_overrides(pairs: &[String]) -> Result<Value, String> {
+    let mut overrides = Map::new();
+    for pair in pairs {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("expected key=value, got '{}'", pair))?;
+        overrides.insert(key.to_string(), Value::String(value.to_string()));
+    }
+    Ok(Value::Object(overrides))
+}
+
+/// Deep-merges `overlay` onto `base`: nested objects are merged key by key,
+/// and any other value in `overlay` wins over the corresponding value in
+/// `base`. Keys present only in `base` are kept.
+fn merge(base: Value, overlay: Value) -> Value {
+    match (base, overlay) {
+        (Value::Object(mut base_map), Value::Object(overlay_map)) => {
+            for (key, overlay_value) in overlay_map {
+                let merged = match base_map.remove(&key) {
+                    Some(base_value) => merge(base_value, overlay_value),
+                    None => overlay_value,
+                };
+                base_map.insert(key, merged);
+            }
+            Value::Object(base_map)
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+#[cfg(test)]
+mod merge_tests {
+    use super::*;
+
+    #[test]
+    fn overlay_scalar_wins_over_base_scalar() {
+        let base = serde_json::json!({"parameter_1": "base"});
+        let overlay = serde_json::json!({"parameter_1": "overlay"});
+        assert_eq!(merge(base, overlay), serde_json::json!({"parameter_1": "overlay"}));
+    }
+
+    #[test]
+    fn overlay_and_base_keys_both_survive() {
+        let base = serde_json::json!({"parameter_1": "base"});
+        let overlay = serde_json::json!({"parameter_2": "overlay"});
+        assert_eq!(
+            merge(base, overlay),
+            serde_json::json!({"parameter_1": "base", "parameter_2": "overlay"})
+        );
+    }
+
+    #[test]
+    fn nested_objects_merge_recursively() {
+        let base = serde_json::json!({"nested": {"a": 1, "b": 2}});
+        let overlay = serde_json::json!({"nested": {"b": 3, "c": 4}});
+        assert_eq!(
+            merge(base, overlay),
+            serde_json::json!({"nested": {"a": 1, "b": 3, "c": 4}})
+        );
+    }
+
+    #[test]
+    fn overlay_scalar_replaces_base_object() {
+        let base = serde_json::json!({"parameter_1": {"a": 1}});
+        let overlay = serde_json::json!({"parameter_1": "scalar"});
+        assert_eq!(merge(base, overlay), serde_json::json!({"parameter_1": "scalar"}));
+    }
+}
+
+/// Resolves the `--config` argument, trying each accepted shape in turn:
+/// an existing file path, a literal JSON object, then comma-separated
+/// `key=value` pairs (with `.`-separated keys building nested objects).
+fn parse_config_arg(
+    config_arg: &str,
+    format_override: Option<&str>,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    if Path::new(config_arg).is_file() {
+        return load_config(config_arg, format_override);
+    }
+    if let Ok(value) = serde_json::from_str::<Value>(config_arg) {
+        return Ok(value);
+    }
+    parse_key_value_pairs(config_arg)
+}
+
+/// Parses comma-separated `key=value` pairs into a JSON object, splitting
+/// dotted keys like `nested.a` into nested objects via `nested_set`.
+fn parse_key_value_pairs(config_arg: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut map = Map::new();
+    for pair in config_arg.split(',') {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("expected key=value, got '{}'", pair))?;
+        let (head, rest) = match key.split_once('.') {
+            Some((head, rest)) => (head, Some(rest)),
+            None => (key, None),
+        };
+        nested_set(&mut map, head, rest, Value::String(value.to_string()))?;
+    }
+    Ok(Value::Object(map))
+}
+
+/// Inserts `value` into `map` at `head`, or, if `rest` is present, recurses
+/// into (creating if needed) the nested object at `head` and continues
+/// resolving the remaining dotted path there.
+fn nested_set(
+    map: &mut Map<String, Value>,
+    head: &str,
+    rest: Option<&str>,
+    value: Value,
+) -> Result<(), String> {
+    match rest {
+        None => {
+            if matches!(map.get(head), Some(Value::Object(_))) {
+                return Err(format!(
+                    "key '{}' was already set as a nested object",
+                    head
+                ));
+            }
+            map.insert(head.to_string(), value);
+            Ok(())
+        }
+        Some(rest) => {
+            let (next_head, next_rest) = match rest.split_once('.') {
+                Some((head, rest)) => (head, Some(rest)),
+                None => (rest, None),
+            };
+            let entry = map
+                .entry(head.to_string())
+                .or_insert_with(|| Value::Object(Map::new()));
+            match entry {
+                Value::Object(inner) => nested_set(inner, next_head, next_rest, value),
+                _ => Err(format!("key '{}' was already set to a scalar value", head)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod key_value_tests {
+    use super::*;
+
+    #[test]
+    fn flat_pairs_become_a_flat_object() {
+        let value = parse_key_value_pairs("parameter_1=foo,parameter_2=bar").unwrap();
+        assert_eq!(value, serde_json::json!({"parameter_1": "foo", "parameter_2": "bar"}));
+    }
+
+    #[test]
+    fn dotted_keys_build_nested_objects() {
+        let value = parse_key_value_pairs("nested.a=1,nested.b=2").unwrap();
+        assert_eq!(value, serde_json::json!({"nested": {"a": "1", "b": "2"}}));
+    }
+
+    #[test]
+    fn pair_without_equals_sign_errors() {
+        assert!(parse_key_value_pairs("parameter_1").is_err());
+    }
+
+    #[test]
+    fn nested_assignment_after_scalar_errors() {
+        let err = parse_key_value_pairs("parameter_2=foo,parameter_2.nested=bar").unwrap_err();
+        assert!(err.to_string().contains("parameter_2"));
+    }
+
+    #[test]
+    fn scalar_assignment_after_nested_errors() {
+        let err = parse_key_value_pairs("parameter_2.nested=bar,parameter_2=foo").unwrap_err();
+        assert!(err.to_string().contains("parameter_2"));
+    }
+}
+
+/// Determines which format to parse `config_file` as, preferring an explicit
+/// `--format` override and otherwise inferring it from the file extension.
+fn resolve_format(config_file: &str, format_override: Option<&str>) -> String {
+    match format_override {
+        Some(fmt) => fmt.to_lowercase(),
+        None => Path::new(config_file)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("")
+            .to_lowercase(),
+    }
+}
+
+fn load_config(
+    config_file: &str,
+    format_override: Option<&str>,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let stage_start = Instant::now();
     let mut file = File::open(config_file)?;
     let mut contents = String::new();
     file.read_to_string(&mut contents)?;
-    let json: Value = serde_json::from_str(&contents)?;
-    Ok(json)
+    log_stage_time("read config file", stage_start);
+
+    let format = resolve_format(config_file, format_override);
+    match format.as_str() {
+        "json" => Ok(serde_json::from_str(&contents)?),
+        "yaml" | "yml" => Ok(serde_yaml::from_str(&contents)?),
+        "toml" => {
+            let value: toml::Value = toml::from_str(&contents)?;
+            Ok(serde_json::to_value(value)?)
+        }
+        "ini" => {
+            let ini = ini::Ini::load_from_str(&contents)?;
+            Ok(ini_to_json(&ini))
+        }
+        other => Err(format!(
+            "Unsupported config file extension: '.{}' (supported: json, yaml, yml, toml, ini; use --format to override)",
+            other
+        )
+        .into()),
+    }
+}
+
+/// Converts a parsed INI document into the same `serde_json::Value` shape
+/// the other formats produce: an object of sections, each an object of
+/// key/value string pairs. Keys outside any section are placed at the root.
+fn ini_to_json(ini: &ini::Ini) -> Value {
+    let mut root = Map::new();
+    for (section, properties) in ini.iter() {
+        let mut entries = Map::new();
+        for (key, value) in properties.iter() {
+            entries.insert(key.to_string(), Value::String(value.to_string()));
+        }
+        match section {
+            Some(name) => {
+                root.insert(name.to_string(), Value::Object(entries));
+            }
+            None => root.extend(entries),
+        }
+    }
+    Value::Object(root)
 }